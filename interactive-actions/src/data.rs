@@ -39,9 +39,28 @@ pub struct Action {
     #[serde(default)]
     pub break_if_cancel: bool,
 
-    /// captures the output of the script, otherwise, stream to screen in real time
+    /// captures the script's stdout/stderr into the transcript; otherwise the
+    /// script inherits the parent's stdio and streams to screen in real time.
+    /// Trade-off: an uncaptured action's `stdout`/`stderr` are absent from the
+    /// [`crate::transcript::Transcript`], since buffering them would mean
+    /// losing the live passthrough (no output until the process exits, and
+    /// no interleaving with a child's own prompts, e.g. `sudo -p`). Setting
+    /// `out` also forces capture, since reading the output requires it.
     #[serde(default)]
     pub capture: bool,
+
+    /// if set, store the script's trimmed stdout into the `VarBag` under this
+    /// name, so later actions can use it via `{{name}}` (or `$NAME` in `run`).
+    /// implies `capture`.
+    #[serde(default)]
+    pub out: Option<String>,
+
+    /// guard expression evaluated against the `VarBag`; the action is skipped
+    /// entirely (no interaction, no script) when it evaluates to false. Supports
+    /// a bare `name` (truthy if set, non-empty and not `"false"`), `name == value`,
+    /// `name != value`, and `name in [a, b, c]`, with `{{var}}` substitution applied first.
+    #[serde(default)]
+    pub when: Option<String>,
 }
 ///
 /// result of the [`Action`]
@@ -74,6 +93,12 @@ pub enum InteractionKind {
     Input,
     #[serde(rename = "select")]
     Select,
+    #[serde(rename = "multi_select")]
+    MultiSelect,
+    #[serde(rename = "password")]
+    Password,
+    #[serde(rename = "editor")]
+    Editor,
 }
 
 #[allow(missing_docs)]
@@ -84,6 +109,11 @@ pub enum Response {
     None,
 }
 
+/// the keystroke a user types to ask for a longer explanation instead of answering
+const EXPLAIN_SENTINEL: &str = "e";
+/// synthetic choice appended to `Select` options so the explanation is reachable from the list
+const EXPLAIN_CHOICE: &str = "(e) explain";
+
 ///
 /// [`Interaction`] models an interactive session with a user declaratively
 /// You can pick from _confirm_, _input_, and other modes of prompting.
@@ -98,6 +128,16 @@ pub struct Interaction {
 
     /// define the set of options just for kind=select
     pub options: Option<Vec<String>>,
+
+    /// longer rationale shown when the user asks to explain (`e`) instead of answering.
+    /// only offered for `confirm` and `select` kinds.
+    #[serde(default)]
+    pub explain: Option<String>,
+
+    /// default value: the pre-filled answer for `input`, or the pre-selected
+    /// option (matched against `options`) for `select`
+    #[serde(default)]
+    pub default: Option<String>,
 }
 impl Interaction {
     fn update_varbag(&self, input: &str, varbag: Option<&mut VarBag>) {
@@ -108,6 +148,34 @@ impl Interaction {
         });
     }
 
+    /// whether this kind supports the explain affordance
+    fn explainable(&self) -> bool {
+        self.explain.is_some() && matches!(self.kind, InteractionKind::Confirm | InteractionKind::Select)
+    }
+
+    /// does this answer mean "explain, don't answer yet"?
+    fn is_explain_answer(&self, answer: &Answer) -> bool {
+        if !self.explainable() {
+            return false;
+        }
+        match answer {
+            Answer::String(input) => input.eq_ignore_ascii_case(EXPLAIN_SENTINEL),
+            Answer::ListItem(selected) => selected.text == EXPLAIN_CHOICE,
+            _ => false,
+        }
+    }
+
+    /// print the `explain` text, indented, so it stands out from the re-asked prompt
+    fn print_explain(&self) {
+        if let Some(explain) = &self.explain {
+            println!();
+            for line in explain.lines() {
+                println!("    {line}");
+            }
+            println!();
+        }
+    }
+
     /// Play an interaction
     ///
     /// # Errors
@@ -116,51 +184,128 @@ impl Interaction {
     pub fn play(
         &self,
         varbag: Option<&mut VarBag>,
-        events: Option<&mut TestEvents<IntoIter<KeyEvent>>>,
+        mut events: Option<&mut TestEvents<IntoIter<KeyEvent>>>,
     ) -> Result<Response> {
-        let question = self.to_question();
-        let answer = if let Some(events) = events {
-            let mut backend = TestBackend::new(Size::from((50, 20)));
-            requestty::prompt_one_with(question, &mut backend, events)
-        } else {
-            requestty::prompt_one(question)
-        }?;
+        loop {
+            let question = self.to_question();
+            let answer = if let Some(events) = events.as_deref_mut() {
+                let mut backend = TestBackend::new(Size::from((50, 20)));
+                requestty::prompt_one_with(question, &mut backend, events)
+            } else {
+                requestty::prompt_one(question)
+            }?;
 
-        Ok(match answer {
-            Answer::String(input) => {
-                self.update_varbag(&input, varbag);
-
-                Response::Text(input)
-            }
-            Answer::ListItem(selected) => {
-                self.update_varbag(&selected.text, varbag);
-                Response::Text(selected.text)
+            if self.is_explain_answer(&answer) {
+                self.print_explain();
+                continue;
             }
-            Answer::Bool(confirmed) if confirmed => {
-                let as_string = "true".to_string();
-                self.update_varbag(&as_string, varbag);
-                Response::Text(as_string)
-            }
-            _ => {
-                Response::Cancel
-                // not supported question types
-            }
-        })
+
+            return Ok(match answer {
+                // a confirm-with-explain is asked as free text (see `to_question`), so
+                // interpret it the way the plain y/n confirm would have
+                Answer::String(input) if matches!(self.kind, InteractionKind::Confirm) => {
+                    if input.eq_ignore_ascii_case("y") || input.eq_ignore_ascii_case("yes") {
+                        let as_string = "true".to_string();
+                        self.update_varbag(&as_string, varbag);
+                        Response::Text(as_string)
+                    } else {
+                        Response::Cancel
+                    }
+                }
+                // requestty's input widget returns "" on a bare Enter; fall back to
+                // the declared default rather than propagating an empty answer
+                Answer::String(input)
+                    if matches!(self.kind, InteractionKind::Input)
+                        && input.is_empty()
+                        && self.default.is_some() =>
+                {
+                    let value = self.default.clone().unwrap_or_default();
+                    self.update_varbag(&value, varbag);
+                    Response::Text(value)
+                }
+                Answer::String(input) => {
+                    self.update_varbag(&input, varbag);
+
+                    Response::Text(input)
+                }
+                Answer::ListItem(selected) => {
+                    self.update_varbag(&selected.text, varbag);
+                    Response::Text(selected.text)
+                }
+                // MultiSelect is stored in the VarBag as a comma-delimited string
+                // (e.g. `bus,train`) rather than JSON, so `{{var}}` substitution in
+                // `run` scripts stays a plain string swap like every other kind.
+                Answer::ListItems(selected) => {
+                    let joined = selected
+                        .into_iter()
+                        .map(|item| item.text)
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    self.update_varbag(&joined, varbag);
+                    Response::Text(joined)
+                }
+                Answer::Bool(confirmed) if confirmed => {
+                    let as_string = "true".to_string();
+                    self.update_varbag(&as_string, varbag);
+                    Response::Text(as_string)
+                }
+                _ => {
+                    Response::Cancel
+                    // not supported question types
+                }
+            });
+        }
     }
 
     /// Convert the interaction into a question
     pub fn to_question(&self) -> Question<'_> {
+        // the affordance differs by kind: typing `e` only works where the answer
+        // is free text (Confirm-as-input); Select instead needs an extra list item
+        // to navigate to, so the hint has to say so rather than promising a keystroke.
+        let hint = if !self.explainable() {
+            self.prompt.clone()
+        } else if matches!(self.kind, InteractionKind::Select) {
+            format!(r#"{} (navigate to "{EXPLAIN_CHOICE}" to explain)"#, self.prompt)
+        } else {
+            format!("{} ({EXPLAIN_SENTINEL} to explain)", self.prompt)
+        };
+
         match self.kind {
-            InteractionKind::Input => Question::input("question")
-                .message(self.prompt.clone())
-                .build(),
-            InteractionKind::Select => Question::select("question")
-                .message(self.prompt.clone())
+            InteractionKind::Input => {
+                let mut question = Question::input("question").message(hint);
+                if let Some(default) = self.default.clone() {
+                    question = question.default(default);
+                }
+                question.build()
+            }
+            InteractionKind::MultiSelect => Question::multi_select("question")
+                .message(hint)
                 .choices(self.options.clone().unwrap_or_default())
                 .build(),
-            InteractionKind::Confirm => Question::confirm("question")
-                .message(self.prompt.clone())
-                .build(),
+            InteractionKind::Password => Question::password("question").message(hint).build(),
+            InteractionKind::Editor => Question::editor("question").message(hint).build(),
+            InteractionKind::Select => {
+                let mut choices = self.options.clone().unwrap_or_default();
+                if self.explainable() {
+                    choices.push(EXPLAIN_CHOICE.to_string());
+                }
+                let mut question = Question::select("question").message(hint);
+                if let Some(idx) = self
+                    .default
+                    .as_ref()
+                    .and_then(|default| choices.iter().position(|choice| choice == default))
+                {
+                    question = question.default(idx);
+                }
+                question.choices(choices).build()
+            }
+            // requestty's confirm is a plain y/n widget with no room for a third
+            // "explain" outcome, so when explain is set we fall back to free text
+            // and parse y/n/e ourselves in `play`.
+            InteractionKind::Confirm if self.explainable() => {
+                Question::input("question").message(hint).build()
+            }
+            InteractionKind::Confirm => Question::confirm("question").message(hint).build(),
         }
     }
 }
@@ -80,13 +80,16 @@
 #![allow(clippy::missing_const_for_fn)]
 
 pub mod data;
+pub mod transcript;
 
 use anyhow::{Error, Result};
-use data::{Action, ActionHook, ActionResult, Response, RunResult, VarBag};
+use data::{Action, ActionHook, ActionResult, InteractionKind, Response, RunResult, VarBag};
 use requestty_ui::events::{KeyEvent, TestEvents};
 use run_script::IoOptions;
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::vec::IntoIter;
+use transcript::{Transcript, TranscriptStep};
 
 ///
 /// Runs [`Action`]s and keeps track of variables in `varbag`.
@@ -95,6 +98,12 @@ use std::vec::IntoIter;
 pub struct ActionRunner {
     /// synthetic events to be injected to prompts, useful in tests
     pub events: Option<TestEvents<IntoIter<KeyEvent>>>,
+    /// the transcript accumulated across calls to [`Self::run`]
+    transcript: Transcript,
+    /// raw values ever answered to a `password` interaction in this run, so they
+    /// can be scrubbed out of the transcript even after flowing into a later
+    /// action's expanded script (e.g. `out: pass` then `run: mysql -p{{pass}}`)
+    secrets: BTreeSet<String>,
 }
 
 impl ActionRunner {
@@ -103,9 +112,16 @@ impl ActionRunner {
     pub fn with_events(events: Vec<KeyEvent>) -> Self {
         Self {
             events: Some(TestEvents::new(events)),
+            transcript: Transcript::default(),
+            secrets: BTreeSet::new(),
         }
     }
 
+    /// the transcript accumulated so far across calls to [`Self::run`]
+    pub fn transcript(&self) -> &Transcript {
+        &self.transcript
+    }
+
     /// Runs actions
     ///
     /// # Errors
@@ -127,6 +143,28 @@ impl ActionRunner {
             .iter()
             .filter(|action| action.hook == hook)
             .map(|action| {
+                // `when` is evaluated right after the hook filter: a false guard
+                // skips the action entirely, with no interaction and no script.
+                if let Some(when) = action.when.as_ref() {
+                    if !eval_when(when, varbag) {
+                        let action_result = ActionResult {
+                            name: action.name.clone(),
+                            run: None,
+                            response: Response::None,
+                        };
+                        self.transcript.steps.push(TranscriptStep {
+                            name: action_result.name.clone(),
+                            prompt: None,
+                            response: Some(Response::None),
+                            script: None,
+                            stdout: None,
+                            stderr: None,
+                            exit_code: None,
+                        });
+                        return Ok(action_result);
+                    }
+                }
+
                 // get interactive response from the user if any is defined
                 if let Some(ref progress) = progress {
                     progress(action);
@@ -139,8 +177,19 @@ impl ActionRunner {
                         interaction.play(Some(varbag), self.events.as_mut())
                     });
 
+                // remember the raw value so it can be scrubbed from the transcript
+                // wherever it turns up later, not just on this action's own step
+                if matches!(
+                    action.interaction.as_ref().map(|i| &i.kind),
+                    Some(InteractionKind::Password)
+                ) {
+                    if let Ok(Response::Text(value)) = &response {
+                        self.secrets.insert(value.clone());
+                    }
+                }
+
                 // with the defined run script and user response, perform an action
-                response.and_then(|r| match (r, action.run.as_ref()) {
+                let action_result = response.and_then(|r| match (r, action.run.as_ref()) {
                     (Response::Cancel, _) => {
                         if action.break_if_cancel {
                             Err(anyhow::anyhow!("stop requested (break_if_cancel)"))
@@ -160,12 +209,25 @@ impl ActionRunner {
                     (resp, Some(run)) => {
                         let mut options = run_script::ScriptOptions::new();
                         options.working_directory = working_dir.map(std::path::Path::to_path_buf);
-                        options.output_redirection = if action.capture {
+                        // only pipe (and thus buffer into the transcript) when something
+                        // actually needs the captured bytes; otherwise inherit the
+                        // parent's stdio so long-running builds, progress bars, and
+                        // child prompts (e.g. `sudo -p`) still stream live instead of
+                        // going silent until the process exits
+                        options.output_redirection = if action.capture || action.out.is_some() {
                             IoOptions::Pipe
                         } else {
                             IoOptions::Inherit
                         };
                         options.print_commands = true;
+                        // expose every VarBag entry as an (uppercased) env var, so
+                        // scripts can read `$CITY` in addition to `{{city}}`
+                        options.env_vars = Some(
+                            varbag
+                                .iter()
+                                .map(|(k, v)| (k.to_uppercase(), v.clone()))
+                                .collect(),
+                        );
                         let args = vec![];
 
                         // varbag replacements: {{interaction.outvar}} -> value
@@ -185,23 +247,142 @@ impl ActionRunner {
                                 }
                                 Ok(tup)
                             })
-                            .map(|(code, out, err)| ActionResult {
-                                name: action.name.clone(),
-                                run: Some(RunResult {
-                                    script,
-                                    code,
-                                    out,
-                                    err,
-                                }),
-                                response: resp,
+                            .map(|(code, out, err)| {
+                                if let Some(out_var) = action.out.as_ref() {
+                                    varbag.insert(out_var.clone(), out.trim().to_string());
+                                }
+                                ActionResult {
+                                    name: action.name.clone(),
+                                    run: Some(RunResult {
+                                        script,
+                                        code,
+                                        out,
+                                        err,
+                                    }),
+                                    response: resp,
+                                }
                             })
                     }
-                })
+                })?;
+
+                // passwords are never echoed into the transcript, even when the
+                // interaction answer itself flows into the VarBag and later scripts
+                let is_password = matches!(
+                    action.interaction.as_ref().map(|i| &i.kind),
+                    Some(InteractionKind::Password)
+                );
+
+                // ... and neither is the raw secret text itself, wherever it has been
+                // substituted into a later action's script/stdout/stderr
+                let script = action_result
+                    .run
+                    .as_ref()
+                    .map(|r| redact(&r.script, &self.secrets));
+                // stdout/stderr were only actually piped (and thus worth recording)
+                // when the action captured -- otherwise they were inherited straight
+                // to the screen and there's nothing here but an empty string
+                let piped = action.capture || action.out.is_some();
+                let stdout = action_result
+                    .run
+                    .as_ref()
+                    .filter(|_| piped)
+                    .map(|r| redact(&r.out, &self.secrets));
+                let stderr = action_result
+                    .run
+                    .as_ref()
+                    .filter(|_| piped)
+                    .map(|r| redact(&r.err, &self.secrets));
+
+                self.transcript.steps.push(TranscriptStep {
+                    name: action_result.name.clone(),
+                    prompt: action.interaction.as_ref().map(|i| i.prompt.clone()),
+                    response: if is_password {
+                        None
+                    } else {
+                        Some(action_result.response.clone())
+                    },
+                    script,
+                    stdout,
+                    stderr,
+                    exit_code: action_result.run.as_ref().map(|r| r.code),
+                });
+
+                Ok(action_result)
             })
             .collect::<Result<Vec<_>>>()
     }
 }
 
+/// Replace every known secret value with `[redacted]`, wherever it appears.
+fn redact(text: &str, secrets: &BTreeSet<String>) -> String {
+    secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .fold(text.to_string(), |acc, secret| acc.replace(secret.as_str(), "[redacted]"))
+}
+
+/// Substitute every `{{var}}` token in `text` with its `VarBag` value.
+fn substitute(text: &str, varbag: &VarBag) -> String {
+    varbag
+        .iter()
+        .fold(text.to_string(), |acc, (k, v)| acc.replace(&format!("{{{{{}}}}}", k), v))
+}
+
+/// Resolve one side of a `when` comparison: if it still contains a `{{var}}`
+/// token it's templated, so substitute it and use the resulting literal
+/// as-is; otherwise it's the bare-name form (e.g. `transport == bus`), so
+/// look it up in the `VarBag` by name (falling back to the text itself).
+///
+/// Deciding this from the *raw*, not-yet-substituted text is what keeps
+/// `{{transport}} == bus` (substitutes to the literal `bus`) from being
+/// re-looked-up as a `VarBag` entry literally named `bus`.
+fn resolve_when_operand(raw: &str, varbag: &VarBag) -> String {
+    let raw = raw.trim();
+    if raw.contains("{{") {
+        substitute(raw, varbag).trim().to_string()
+    } else {
+        varbag
+            .get(raw)
+            .map_or_else(|| raw.to_string(), |value| value.trim().to_string())
+    }
+}
+
+/// Evaluate an [`Action`]'s `when` guard against the current `VarBag`.
+///
+/// The trimmed expression is parsed as one of: bare `name` (truthy if
+/// present, non-empty, and not `"false"`), `name == value`, `name != value`,
+/// or `name in [a, b, c]`, comparing everything as trimmed strings. Each
+/// side is template-substituted (see [`resolve_when_operand`]) before
+/// comparing.
+fn eval_when(expr: &str, varbag: &VarBag) -> bool {
+    let expr = expr.trim();
+
+    if let Some((name, value)) = expr.split_once("!=") {
+        return resolve_when_operand(name, varbag) != substitute(value, varbag).trim();
+    }
+    if let Some((name, value)) = expr.split_once("==") {
+        return resolve_when_operand(name, varbag) == substitute(value, varbag).trim();
+    }
+    if let Some((name, rest)) = expr.split_once(" in ") {
+        let value = resolve_when_operand(name, varbag);
+        let options = substitute(rest.trim(), varbag);
+        return options
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .any(|option| option.trim() == value);
+    }
+
+    if expr.contains("{{") {
+        let value = substitute(expr, varbag);
+        let value = value.trim();
+        return !value.is_empty() && value != "false";
+    }
+    varbag
+        .get(expr)
+        .is_some_and(|value| !value.is_empty() && value != "false")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,4 +478,299 @@ mod tests {
 
         assert_debug_snapshot!(v);
     }
+
+    #[test]
+    fn test_explain_affordance() {
+        let actions_defs: Vec<Action> = serde_yaml::from_str(
+            r#"
+- name: confirm-action
+  interaction:
+    kind: confirm
+    prompt: are you sure?
+    explain: this will delete all local branches
+    out: confirm
+"#,
+        )
+        .unwrap();
+        let events = vec![
+            KeyCode::Char('e').into(), // ask to explain first...
+            KeyCode::Enter.into(),     //
+            KeyCode::Char('y').into(), // ...then actually answer
+            KeyCode::Enter.into(),     //
+        ];
+        let mut actions = ActionRunner::with_events(events);
+        let mut v = VarBag::new();
+        assert_debug_snapshot!(actions
+            .run(
+                &actions_defs,
+                Some(Path::new(".")),
+                &mut v,
+                ActionHook::After,
+                None::<&fn(&Action) -> ()>
+            )
+            .unwrap());
+        assert_debug_snapshot!(v);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_transcript_capture_depends_on_capture_and_out() {
+        let actions_defs: Vec<Action> = serde_yaml::from_str(
+            r#"
+- name: input-action
+  interaction:
+    kind: input
+    prompt: which city?
+    default: dallas
+    out: city
+  run: echo {{city}}
+- name: captured-action
+  run: echo captured
+  capture: true
+"#,
+        )
+        .unwrap();
+        let events = vec![
+            KeyCode::Char('t').into(),
+            KeyCode::Char('l').into(),
+            KeyCode::Char('v').into(),
+            KeyCode::Enter.into(),
+        ];
+        let mut actions = ActionRunner::with_events(events);
+        let mut v = VarBag::new();
+        actions
+            .run(
+                &actions_defs,
+                Some(Path::new(".")),
+                &mut v,
+                ActionHook::After,
+                None::<&fn(&Action) -> ()>,
+            )
+            .unwrap();
+
+        // neither `capture` nor an action-level `out` was set, so stdio was
+        // inherited (streamed live) and the transcript has nothing to show
+        let uncaptured = &actions.transcript().steps[0];
+        assert_eq!(uncaptured.stdout, None);
+        assert_eq!(uncaptured.script.as_deref(), Some("echo tlv"));
+
+        // `capture: true` forces a pipe, so the transcript does get the output
+        let captured = &actions.transcript().steps[1];
+        assert_eq!(captured.stdout.as_deref(), Some("captured\n"));
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_multi_select_and_password_scrubbed() {
+        let actions_defs: Vec<Action> = serde_yaml::from_str(
+            r#"
+- name: transports
+  interaction:
+    kind: multi_select
+    prompt: pick transports
+    options:
+    - bus
+    - train
+    - walk
+    out: transports
+- name: db-password
+  interaction:
+    kind: password
+    prompt: database password
+    out: pass
+  run: echo connecting with {{pass}}
+"#,
+        )
+        .unwrap();
+        let events = vec![
+            KeyCode::Char(' ').into(), // toggle bus
+            KeyCode::Down.into(),
+            KeyCode::Char(' ').into(), // toggle train
+            KeyCode::Enter.into(),     // confirm the multi-select
+            KeyCode::Char('s').into(), // password: s3cret
+            KeyCode::Char('3').into(),
+            KeyCode::Char('c').into(),
+            KeyCode::Char('r').into(),
+            KeyCode::Char('e').into(),
+            KeyCode::Char('t').into(),
+            KeyCode::Enter.into(),
+        ];
+        let mut actions = ActionRunner::with_events(events);
+        let mut v = VarBag::new();
+        actions
+            .run(
+                &actions_defs,
+                Some(Path::new(".")),
+                &mut v,
+                ActionHook::After,
+                None::<&fn(&Action) -> ()>,
+            )
+            .unwrap();
+
+        // MultiSelect is a comma-delimited string in the VarBag
+        assert_eq!(v.get("transports").map(String::as_str), Some("bus,train"));
+        assert_eq!(v.get("pass").map(String::as_str), Some("s3cret"));
+
+        // the password never shows up anywhere in the transcript, including in
+        // the later action's expanded script
+        let json = actions.transcript().to_json().unwrap();
+        assert!(!json.contains("s3cret"));
+        assert!(json.contains("[redacted]"));
+    }
+
+    #[test]
+    fn test_input_default_on_empty_enter() {
+        let actions_defs: Vec<Action> = serde_yaml::from_str(
+            r#"
+- name: input-action
+  interaction:
+    kind: input
+    prompt: which city?
+    default: dallas
+    out: city
+"#,
+        )
+        .unwrap();
+        // press Enter straight away, without typing anything
+        let events = vec![KeyCode::Enter.into()];
+        let mut actions = ActionRunner::with_events(events);
+        let mut v = VarBag::new();
+        actions
+            .run(
+                &actions_defs,
+                Some(Path::new(".")),
+                &mut v,
+                ActionHook::After,
+                None::<&fn(&Action) -> ()>,
+            )
+            .unwrap();
+
+        assert_eq!(v.get("city").map(String::as_str), Some("dallas"));
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_when_guard_evaluates_templated_var() {
+        let actions_defs: Vec<Action> = serde_yaml::from_str(
+            r#"
+- name: transport
+  interaction:
+    kind: select
+    prompt: pick transport
+    options:
+    - bus
+    - train
+    out: transport
+- name: bus-only
+  when: "{{transport}} == bus"
+  run: echo only for bus
+  out: bus_note
+- name: train-only
+  when: "{{transport}} == train"
+  run: echo only for train
+  out: train_note
+"#,
+        )
+        .unwrap();
+        let events = vec![KeyCode::Enter.into()]; // accept the first option: bus
+        let mut actions = ActionRunner::with_events(events);
+        let mut v = VarBag::new();
+        actions
+            .run(
+                &actions_defs,
+                Some(Path::new(".")),
+                &mut v,
+                ActionHook::After,
+                None::<&fn(&Action) -> ()>,
+            )
+            .unwrap();
+
+        // the `{{transport}}` templated guard correctly matches the selected value...
+        assert_eq!(v.get("bus_note").map(String::as_str), Some("only for bus"));
+        // ...and the non-matching guard skips its action entirely
+        assert_eq!(v.get("train_note"), None);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_when_guard_rhs_literal_not_resolved_against_unrelated_var() {
+        let actions_defs: Vec<Action> = serde_yaml::from_str(
+            r#"
+- name: transport
+  interaction:
+    kind: select
+    prompt: pick transport
+    options:
+    - bus
+    - train
+    out: transport
+- name: bus-literal
+  interaction:
+    kind: input
+    prompt: set a var named after the RHS literal
+    default: not-the-transport
+    out: bus
+- name: bus-only
+  when: "{{transport}} == bus"
+  run: echo only for bus
+  out: bus_note
+"#,
+        )
+        .unwrap();
+        let events = vec![
+            KeyCode::Enter.into(), // accept the first transport option: bus
+            KeyCode::Enter.into(), // accept the default for the `bus` var: not-the-transport
+        ];
+        let mut actions = ActionRunner::with_events(events);
+        let mut v = VarBag::new();
+        actions
+            .run(
+                &actions_defs,
+                Some(Path::new(".")),
+                &mut v,
+                ActionHook::After,
+                None::<&fn(&Action) -> ()>,
+            )
+            .unwrap();
+
+        // a VarBag entry literally named `bus` (the RHS text) must not be
+        // substituted in for the literal "bus" the guard author wrote
+        assert_eq!(v.get("bus").map(String::as_str), Some("not-the-transport"));
+        assert_eq!(v.get("bus_note").map(String::as_str), Some("only for bus"));
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_out_without_capture_and_env_injection() {
+        let actions_defs: Vec<Action> = serde_yaml::from_str(
+            r#"
+- name: input-action
+  interaction:
+    kind: input
+    prompt: which city?
+    default: dallas
+    out: city
+- name: echo-env
+  run: echo $CITY
+  out: echoed
+"#,
+        )
+        .unwrap();
+        let events = vec![KeyCode::Enter.into()]; // accept default: dallas
+        let mut actions = ActionRunner::with_events(events);
+        let mut v = VarBag::new();
+        actions
+            .run(
+                &actions_defs,
+                Some(Path::new(".")),
+                &mut v,
+                ActionHook::After,
+                None::<&fn(&Action) -> ()>,
+            )
+            .unwrap();
+
+        // `out:` alone (no `capture: true`) still fed stdout into the VarBag,
+        // and the script read the VarBag entry via the injected `$CITY` env var
+        assert_eq!(v.get("echoed").map(String::as_str), Some("dallas"));
+    }
 }
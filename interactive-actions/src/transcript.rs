@@ -0,0 +1,115 @@
+//!
+//! Records a run as a replayable transcript, so whole workflows can be
+//! golden-file tested or turned into documentation/demos, not just
+//! individual actions.
+//!
+use crate::data::Response;
+use anyhow::Result;
+use serde_derive::{Deserialize, Serialize};
+
+///
+/// One recorded step of a run: the prompt shown (if any), the user's
+/// response, the script as actually expanded, and what it produced.
+///
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TranscriptStep {
+    /// name of the action this step belongs to
+    pub name: String,
+    /// the prompt text shown to the user, if the action had an interaction
+    pub prompt: Option<String>,
+    /// the user's response to the prompt, if any; always `None` for a
+    /// `password` interaction
+    pub response: Option<Response>,
+    /// the script as actually run, with `{{var}}` tokens expanded
+    pub script: Option<String>,
+    /// captured stdout, if the script ran with `capture` or `out` set
+    /// (otherwise its output went straight to the screen)
+    pub stdout: Option<String>,
+    /// captured stderr, if the script ran with `capture` or `out` set
+    pub stderr: Option<String>,
+    /// exit code of the script, if it ran
+    pub exit_code: Option<i32>,
+}
+
+///
+/// A replayable record of every [`crate::ActionRunner::run`] call made on
+/// one runner; steps accumulate across calls.
+///
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Transcript {
+    /// steps, in the order they were run
+    pub steps: Vec<TranscriptStep>,
+}
+
+impl Transcript {
+    /// serialize the transcript to JSON
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// render the transcript as a self-contained SVG, resembling a captured
+    /// terminal session: one line per prompt, response, command, and output.
+    pub fn to_svg(&self) -> String {
+        let lines = self.lines();
+        let height = 40 + lines.len() * 18;
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="640" height="{height}" font-family="monospace" font-size="13">"#
+        );
+        svg.push_str(r#"<rect width="100%" height="100%" fill="#0d1117"/>"#);
+        for (i, line) in lines.iter().enumerate() {
+            let y = 24 + i * 18;
+            svg.push_str(&format!(
+                r#"<text x="12" y="{y}" fill="#c9d1d9" xml:space="preserve">{}</text>"#,
+                escape_xml(line)
+            ));
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// render the transcript as self-contained HTML wrapping the same lines
+    pub fn to_html(&self) -> String {
+        let body = self
+            .lines()
+            .iter()
+            .map(|line| format!("<div>{}</div>", escape_xml(line)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "<pre style=\"background:#0d1117;color:#c9d1d9;padding:1em;font-family:monospace\">\n{body}\n</pre>"
+        )
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for step in &self.steps {
+            if let Some(prompt) = &step.prompt {
+                lines.push(format!("? {prompt}"));
+            }
+            if let Some(response) = &step.response {
+                lines.push(format!("> {response:?}"));
+            }
+            if let Some(script) = &step.script {
+                lines.push(format!("$ {script}"));
+            }
+            if let Some(stdout) = &step.stdout {
+                lines.extend(stdout.lines().map(ToString::to_string));
+            }
+            if let Some(stderr) = &step.stderr {
+                lines.extend(stderr.lines().map(ToString::to_string));
+            }
+        }
+        lines
+    }
+}
+
+fn escape_xml(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}